@@ -0,0 +1,272 @@
+//! Parses the build matrix config consumed by the `json2vars-setter` GitHub
+//! Action into a strongly typed [`Config`].
+
+use schemars::{schema_for, JsonSchema, Schema};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+mod matrix;
+mod merge;
+#[cfg(feature = "resolve")]
+mod resolve;
+mod target;
+mod validate;
+
+pub use matrix::MatrixEntry;
+pub use merge::parse_config_with_defaults;
+#[cfg(feature = "resolve")]
+pub use resolve::ResolveOptions;
+pub use target::TargetSpec;
+pub use validate::{validate_config, ValidationIssue};
+
+/// The default `os` list used for any config file that omits it.
+fn default_os() -> Vec<String> {
+    vec![
+        "ubuntu-latest".to_string(),
+        "windows-latest".to_string(),
+        "macos-latest".to_string(),
+    ]
+}
+
+/// The default `ghpages_branch` used for any config file that omits it.
+fn default_ghpages_branch() -> String {
+    "gh-pages".to_string()
+}
+
+/// The build matrix as read from `rust_project_matrix.{json,yaml,toml,json5}`.
+///
+/// Every field is optional on disk: an omitted `os` falls back to
+/// `["ubuntu-latest", "windows-latest", "macos-latest"]`, an omitted
+/// `versions` to an empty map, and an omitted `ghpages_branch` to
+/// `"gh-pages"`. See [`Config::default`] for the exact values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Config {
+    pub os: Vec<String>,
+    pub versions: HashMap<String, Vec<String>>,
+    pub ghpages_branch: String,
+    /// Overrides the looked-up Rust target triple for a self-hosted or
+    /// non-standard `os` runner label, keyed by that `os` entry.
+    pub target_overrides: HashMap<String, String>,
+    /// Per-`os` target metadata, populated by [`Config::with_targets`].
+    /// Empty until then.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub targets: HashMap<String, TargetSpec>,
+    /// GitHub Actions-style extra matrix combinations, merged onto a
+    /// matching [`MatrixEntry`] or appended as a one-off entry by
+    /// [`Config::expand_matrix`].
+    pub include: Vec<MatrixEntry>,
+    /// GitHub Actions-style matrix exclusions: a [`MatrixEntry`] produced by
+    /// [`Config::expand_matrix`]'s cartesian product is dropped if it
+    /// matches every key/value pair in one of these.
+    pub exclude: Vec<MatrixEntry>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            os: default_os(),
+            versions: HashMap::new(),
+            ghpages_branch: default_ghpages_branch(),
+            target_overrides: HashMap::new(),
+            targets: HashMap::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// The JSON Schema describing this struct's on-disk shape, for editor
+    /// autocompletion in workflow files. [`validate_config`] does not read
+    /// this schema: its checks are hand-written, and it rejects unknown keys
+    /// and an empty `os` list, both of which this schema accepts.
+    pub fn json_schema() -> Schema {
+        schema_for!(Config)
+    }
+}
+
+/// File formats `parse_config` knows how to deserialize, resolved from the
+/// input file's extension. `Json` is always available; the others are gated
+/// behind their matching cargo feature so callers only pull in the serde
+/// backends they actually need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfigFormat {
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "json5")]
+    Json5,
+}
+
+impl ConfigFormat {
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(ConfigFormat::Json),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            #[cfg(feature = "toml")]
+            "toml" => Some(ConfigFormat::Toml),
+            #[cfg(feature = "json5")]
+            "json5" => Some(ConfigFormat::Json5),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn deserialize(self, contents: &str) -> Result<Config, String> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| e.to_string()),
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|e| e.to_string()),
+            #[cfg(feature = "json5")]
+            ConfigFormat::Json5 => json5::from_str(contents).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Deserializes into a generic [`serde_json::Value`] instead of
+    /// [`Config`], so callers can inspect the raw shape (extra keys, wrong
+    /// types) before committing to the typed struct.
+    pub(crate) fn deserialize_value(self, contents: &str) -> Result<serde_json::Value, String> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| e.to_string()),
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|e| e.to_string()),
+            #[cfg(feature = "json5")]
+            ConfigFormat::Json5 => json5::from_str(contents).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Reads the matrix config at `path` and deserializes it into a [`Config`].
+///
+/// The format is resolved from the file extension (`.json`, `.yaml`/`.yml`,
+/// `.toml`, `.json5`); formats other than JSON require enabling the matching
+/// cargo feature. Returns `None` on a missing file, an unrecognized
+/// extension, or a deserialization error. When `verbose` is `true`, the
+/// underlying error is printed to stderr before returning `None`.
+pub fn parse_config(path: &Path, verbose: bool) -> Option<Config> {
+    let format = match ConfigFormat::from_path(path) {
+        Some(format) => format,
+        None => {
+            if verbose {
+                eprintln!(
+                    "{}: unrecognized or unsupported config extension",
+                    path.display()
+                );
+            }
+            return None;
+        }
+    };
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            if verbose {
+                eprintln!("{}: {}", path.display(), e);
+            }
+            return None;
+        }
+    };
+
+    match format.deserialize(&contents) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            if verbose {
+                eprintln!("{}: {}", path.display(), e);
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp(suffix: &str, contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn parses_json_by_default() {
+        let file = write_temp(
+            ".json",
+            r#"{"os": ["ubuntu-latest"], "versions": {"rust": ["stable"]}, "ghpages_branch": "gh-pages"}"#,
+        );
+        let config = parse_config(file.path(), false).expect("valid json config");
+        assert_eq!(config.os, vec!["ubuntu-latest"]);
+        assert_eq!(config.ghpages_branch, "gh-pages");
+    }
+
+    #[test]
+    fn rejects_unknown_extension() {
+        let file = write_temp(".ini", "os = ubuntu-latest");
+        assert!(parse_config(file.path(), false).is_none());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn parses_yaml() {
+        let file = write_temp(
+            ".yaml",
+            "os:\n  - ubuntu-latest\nversions:\n  rust:\n    - stable\nghpages_branch: gh-pages\n",
+        );
+        let config = parse_config(file.path(), false).expect("valid yaml config");
+        assert_eq!(config.os, vec!["ubuntu-latest"]);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn parses_toml() {
+        let file = write_temp(
+            ".toml",
+            "os = [\"ubuntu-latest\"]\nghpages_branch = \"gh-pages\"\n\n[versions]\nrust = [\"stable\"]\n",
+        );
+        let config = parse_config(file.path(), false).expect("valid toml config");
+        assert_eq!(config.os, vec!["ubuntu-latest"]);
+    }
+
+    #[cfg(feature = "json5")]
+    #[test]
+    fn parses_json5() {
+        let file = write_temp(
+            ".json5",
+            "{ os: ['ubuntu-latest'], versions: { rust: ['stable'] }, ghpages_branch: 'gh-pages' }",
+        );
+        let config = parse_config(file.path(), false).expect("valid json5 config");
+        assert_eq!(config.os, vec!["ubuntu-latest"]);
+    }
+
+    /// Mirrors rust-analyzer's "schema in sync" sourcegen check: regenerate
+    /// the schema and fail if the committed copy has drifted. Regenerate with
+    /// `UPDATE_SCHEMA=1 cargo test -p json2vars_setter_rs schema_is_in_sync`.
+    #[test]
+    fn schema_is_in_sync() {
+        let generated = serde_json::to_string_pretty(&Config::json_schema()).unwrap();
+        let schema_path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema.json");
+
+        if std::env::var_os("UPDATE_SCHEMA").is_some() {
+            std::fs::write(schema_path, format!("{generated}\n")).unwrap();
+            return;
+        }
+
+        let committed = std::fs::read_to_string(schema_path)
+            .expect("schema.json is missing; regenerate it with UPDATE_SCHEMA=1");
+        assert_eq!(
+            committed.trim_end(),
+            generated,
+            "schema.json is stale; regenerate with `UPDATE_SCHEMA=1 cargo test -p json2vars_setter_rs schema_is_in_sync`"
+        );
+    }
+}
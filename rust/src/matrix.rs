@@ -0,0 +1,155 @@
+//! Expands a [`Config`] into the concrete `os` × `versions.<lang>`
+//! combinations a GitHub Actions `strategy.matrix` job would run, honoring
+//! GitHub Actions-style `include`/`exclude` rules.
+
+use crate::Config;
+use std::collections::HashMap;
+
+/// One concrete combination from [`Config::expand_matrix`], serializable to
+/// the shape GitHub Actions expects from `fromJSON(...)`.
+pub type MatrixEntry = HashMap<String, String>;
+
+impl Config {
+    /// Computes the cartesian product of `os` and each `versions.<lang>`
+    /// list, drops any combination matching an `exclude` entry, then merges
+    /// each `include` entry onto every combination it matches on shared keys
+    /// (or appends it as a one-off combination if none match).
+    pub fn expand_matrix(&self) -> Vec<MatrixEntry> {
+        let mut entries = cartesian_product(&self.os, &self.versions);
+        entries.retain(|entry| !self.exclude.iter().any(|ex| is_subset_match(entry, ex)));
+
+        let dimension_keys = self.dimension_keys();
+        for include in &self.include {
+            let shared_keys: Vec<&String> = include
+                .keys()
+                .filter(|key| dimension_keys.contains(key.as_str()))
+                .collect();
+
+            let mut matched = false;
+            if !shared_keys.is_empty() {
+                for entry in entries.iter_mut() {
+                    if shared_keys
+                        .iter()
+                        .all(|key| entry.get(*key) == include.get(*key))
+                    {
+                        matched = true;
+                        entry.extend(include.iter().map(|(k, v)| (k.clone(), v.clone())));
+                    }
+                }
+            }
+            if !matched {
+                entries.push(include.clone());
+            }
+        }
+
+        entries
+    }
+
+    fn dimension_keys(&self) -> std::collections::HashSet<&str> {
+        let mut keys: std::collections::HashSet<&str> =
+            self.versions.keys().map(String::as_str).collect();
+        keys.insert("os");
+        keys
+    }
+}
+
+fn cartesian_product(os: &[String], versions: &HashMap<String, Vec<String>>) -> Vec<MatrixEntry> {
+    let mut dimensions: Vec<(&str, &[String])> = vec![("os", os)];
+    let mut langs: Vec<&String> = versions.keys().collect();
+    langs.sort();
+    for lang in langs {
+        dimensions.push((lang.as_str(), versions[lang].as_slice()));
+    }
+
+    let mut entries = vec![MatrixEntry::new()];
+    for (key, values) in dimensions {
+        let mut expanded = Vec::with_capacity(entries.len() * values.len());
+        for entry in &entries {
+            for value in values {
+                let mut next = entry.clone();
+                next.insert(key.to_string(), value.clone());
+                expanded.push(next);
+            }
+        }
+        entries = expanded;
+    }
+    entries
+}
+
+fn is_subset_match(entry: &MatrixEntry, pattern: &MatrixEntry) -> bool {
+    pattern
+        .iter()
+        .all(|(key, value)| entry.get(key) == Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(os: Vec<&str>, versions: Vec<(&str, Vec<&str>)>) -> Config {
+        Config {
+            os: os.into_iter().map(String::from).collect(),
+            versions: versions
+                .into_iter()
+                .map(|(lang, vs)| (lang.to_string(), vs.into_iter().map(String::from).collect()))
+                .collect(),
+            ..Config::default()
+        }
+    }
+
+    fn entry(pairs: &[(&str, &str)]) -> MatrixEntry {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn cartesian_product_across_os_and_versions() {
+        let config = config(
+            vec!["ubuntu-latest", "windows-latest"],
+            vec![("rust", vec!["1.84.0", "1.85.0"])],
+        );
+        let matrix = config.expand_matrix();
+        assert_eq!(matrix.len(), 4);
+        assert!(matrix.contains(&entry(&[("os", "ubuntu-latest"), ("rust", "1.84.0")])));
+        assert!(matrix.contains(&entry(&[("os", "windows-latest"), ("rust", "1.85.0")])));
+    }
+
+    #[test]
+    fn exclude_drops_a_matching_pair() {
+        let mut config = config(
+            vec!["ubuntu-latest", "windows-latest"],
+            vec![("rust", vec!["1.80.0", "1.85.0"])],
+        );
+        config.exclude = vec![entry(&[("os", "windows-latest"), ("rust", "1.80.0")])];
+
+        let matrix = config.expand_matrix();
+        assert_eq!(matrix.len(), 3);
+        assert!(!matrix.contains(&entry(&[("os", "windows-latest"), ("rust", "1.80.0")])));
+    }
+
+    #[test]
+    fn include_merges_extra_keys_onto_matching_entries() {
+        let mut config = config(vec!["ubuntu-latest"], vec![("rust", vec!["1.85.0"])]);
+        config.include = vec![entry(&[
+            ("os", "ubuntu-latest"),
+            ("rust", "1.85.0"),
+            ("experimental", "true"),
+        ])];
+
+        let matrix = config.expand_matrix();
+        assert_eq!(matrix.len(), 1);
+        assert_eq!(matrix[0].get("experimental"), Some(&"true".to_string()));
+    }
+
+    #[test]
+    fn include_with_no_match_is_appended_as_a_one_off() {
+        let mut config = config(vec!["ubuntu-latest"], vec![("rust", vec!["1.85.0"])]);
+        config.include = vec![entry(&[("os", "macos-13"), ("rust", "1.70.0")])];
+
+        let matrix = config.expand_matrix();
+        assert_eq!(matrix.len(), 2);
+        assert!(matrix.contains(&entry(&[("os", "macos-13"), ("rust", "1.70.0")])));
+    }
+}
@@ -0,0 +1,78 @@
+//! Deep-merges a partial matrix config file over a caller-supplied base
+//! [`Config`], so a project can keep a shared base matrix and override only
+//! the pieces that differ per repo.
+
+use crate::{Config, ConfigFormat};
+use std::path::Path;
+
+/// Reads the matrix config at `path` and deep-merges it over `base`: any
+/// field (or, for `versions`, any per-language entry) the file sets
+/// overrides `base`'s value, while everything the file omits is kept from
+/// `base`. Returns `None` on a missing file, an unrecognized extension, or a
+/// deserialization error.
+pub fn parse_config_with_defaults(path: &Path, base: Config) -> Option<Config> {
+    let format = ConfigFormat::from_path(path)?;
+    let contents = std::fs::read_to_string(path).ok()?;
+    let overlay = format.deserialize_value(&contents).ok()?;
+    let merged = deep_merge(serde_json::to_value(base).ok()?, overlay);
+    serde_json::from_value(merged).ok()
+}
+
+fn deep_merge(base: serde_json::Value, overlay: serde_json::Value) -> serde_json::Value {
+    match (base, overlay) {
+        (serde_json::Value::Object(mut base_map), serde_json::Value::Object(overlay_map)) => {
+            for (key, overlay_value) in overlay_map {
+                let merged_value = match base_map.remove(&key) {
+                    Some(base_value) => deep_merge(base_value, overlay_value),
+                    None => overlay_value,
+                };
+                base_map.insert(key, merged_value);
+            }
+            serde_json::Value::Object(base_map)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::Write;
+
+    fn write_temp(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    fn base() -> Config {
+        let mut versions = HashMap::new();
+        versions.insert("rust".to_string(), vec!["1.85.0".to_string()]);
+        versions.insert("python".to_string(), vec!["3.12".to_string()]);
+        Config {
+            os: vec!["ubuntu-latest".to_string()],
+            versions,
+            ghpages_branch: "gh-pages".to_string(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn overrides_only_the_fields_it_sets() {
+        let file = write_temp(r#"{"versions": {"rust": ["stable"]}}"#);
+        let merged = parse_config_with_defaults(file.path(), base()).expect("merge succeeds");
+
+        assert_eq!(merged.os, vec!["ubuntu-latest"]);
+        assert_eq!(merged.ghpages_branch, "gh-pages");
+        assert_eq!(merged.versions.get("rust").unwrap(), &vec!["stable"]);
+        assert_eq!(merged.versions.get("python").unwrap(), &vec!["3.12"]);
+    }
+
+    #[test]
+    fn empty_file_falls_back_entirely_to_base() {
+        let file = write_temp("{}");
+        let merged = parse_config_with_defaults(file.path(), base()).expect("merge succeeds");
+        assert_eq!(merged, base());
+    }
+}
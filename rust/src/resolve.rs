@@ -0,0 +1,296 @@
+//! Expands symbolic `versions.rust` channels (`stable`, `beta`, `nightly`,
+//! `MSRV`) into concrete version strings by fetching Rust's dist channel
+//! manifest, the same metadata rustc bootstrap pins via its stage0 file.
+
+use crate::Config;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CHANNELS: [&str; 3] = ["stable", "beta", "nightly"];
+
+/// Controls how [`Config::resolve_versions`] fetches and caches Rust's
+/// release metadata.
+#[derive(Debug, Clone)]
+pub struct ResolveOptions {
+    /// Where the fetched channel manifest is cached between runs.
+    pub cache_path: PathBuf,
+    /// How long a cached manifest stays valid before it is re-fetched.
+    pub cache_ttl: Duration,
+    /// The concrete version substituted for the symbolic `"MSRV"` entry.
+    /// Left unresolved if `None`.
+    pub msrv: Option<String>,
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        Self {
+            cache_path: std::env::temp_dir().join("json2vars-setter-rs-channel-manifest.json"),
+            cache_ttl: Duration::from_secs(24 * 60 * 60),
+            msrv: None,
+        }
+    }
+}
+
+/// One channel's resolved version, mirroring the `date`/`version` pair
+/// rustc bootstrap pins in its stage0 metadata file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChannelVersion {
+    version: String,
+    #[allow(dead_code)]
+    date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestCache {
+    fetched_at_unix: u64,
+    channels: HashMap<String, ChannelVersion>,
+}
+
+impl Config {
+    /// Returns a copy of this config with symbolic `versions.rust` entries
+    /// expanded to concrete version strings. Entries that can't be resolved
+    /// (offline with no usable cache, or `"MSRV"` with no `opts.msrv`) are
+    /// left untouched rather than erroring.
+    pub fn resolve_versions(&self, opts: &ResolveOptions) -> Config {
+        let mut resolved = self.clone();
+        let Some(rust_versions) = resolved.versions.get_mut("rust") else {
+            return resolved;
+        };
+        if !rust_versions.iter().any(|v| is_symbolic(v)) {
+            return resolved;
+        }
+
+        let cache = load_or_fetch(opts);
+        for entry in rust_versions.iter_mut() {
+            if entry == "MSRV" {
+                if let Some(msrv) = &opts.msrv {
+                    *entry = msrv.clone();
+                }
+            } else if let Some(channel) =
+                cache.as_ref().and_then(|c| c.channels.get(entry.as_str()))
+            {
+                *entry = channel.version.clone();
+            }
+        }
+        resolved
+    }
+}
+
+fn is_symbolic(v: &str) -> bool {
+    v == "MSRV" || CHANNELS.contains(&v)
+}
+
+fn load_or_fetch(opts: &ResolveOptions) -> Option<ManifestCache> {
+    if let Some(cache) = read_cache(&opts.cache_path) {
+        if is_fresh(&cache, opts.cache_ttl) {
+            return Some(cache);
+        }
+    }
+
+    match fetch_manifest() {
+        Some(cache) => {
+            write_cache(&opts.cache_path, &cache);
+            Some(cache)
+        }
+        // Offline or unreachable: fall back to a stale cache rather than
+        // leaving every symbolic entry unresolved.
+        None => read_cache(&opts.cache_path),
+    }
+}
+
+fn is_fresh(cache: &ManifestCache, ttl: Duration) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    now.saturating_sub(cache.fetched_at_unix) < ttl.as_secs()
+}
+
+fn read_cache(path: &std::path::Path) -> Option<ManifestCache> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_cache(path: &std::path::Path, cache: &ManifestCache) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(contents) = serde_json::to_string_pretty(cache) {
+        let _ = std::fs::write(path, contents);
+    }
+}
+
+fn fetch_manifest() -> Option<ManifestCache> {
+    let mut channels = HashMap::new();
+    for &channel in &CHANNELS {
+        let url = format!("https://static.rust-lang.org/dist/channel-rust-{channel}.toml");
+        let mut response = ureq::get(&url).call().ok()?;
+        let body = response.body_mut().read_to_string().ok()?;
+        let manifest: DistManifest = toml::from_str(&body).ok()?;
+        channels.insert(
+            channel.to_string(),
+            ChannelVersion {
+                version: manifest.pkg.rust.version,
+                date: manifest.date,
+            },
+        );
+    }
+    Some(ManifestCache {
+        fetched_at_unix: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        channels,
+    })
+}
+
+/// The subset of Rust's dist channel manifest (`channel-rust-*.toml`) we
+/// care about.
+#[derive(Debug, Deserialize)]
+struct DistManifest {
+    date: String,
+    pkg: DistPkg,
+}
+
+#[derive(Debug, Deserialize)]
+struct DistPkg {
+    rust: DistRustPkg,
+}
+
+#[derive(Debug, Deserialize)]
+struct DistRustPkg {
+    version: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as Map;
+
+    fn config_with_rust_versions(versions: Vec<&str>) -> Config {
+        let mut map = Map::new();
+        map.insert(
+            "rust".to_string(),
+            versions.into_iter().map(String::from).collect(),
+        );
+        Config {
+            os: vec!["ubuntu-latest".to_string()],
+            versions: map,
+            ghpages_branch: "gh-pages".to_string(),
+            ..Config::default()
+        }
+    }
+
+    /// A config with no symbolic `versions.rust` entries never consults the
+    /// cache, so `cache_path` pointing nowhere is fine here: there's nothing
+    /// to resolve and `fetch_manifest` is never reached.
+    #[test]
+    fn leaves_concrete_versions_untouched() {
+        let config = config_with_rust_versions(vec!["1.85.0"]);
+        let opts = ResolveOptions {
+            cache_path: std::env::temp_dir().join("json2vars-setter-rs-test-no-such-cache.json"),
+            ..Default::default()
+        };
+        let resolved = config.resolve_versions(&opts);
+        assert_eq!(resolved.versions["rust"], vec!["1.85.0".to_string()]);
+    }
+
+    #[test]
+    fn resolves_from_a_fresh_cache() {
+        let cache_path = std::env::temp_dir().join(format!(
+            "json2vars-setter-rs-test-{}-fresh-cache.json",
+            std::process::id()
+        ));
+        let mut channels = Map::new();
+        channels.insert(
+            "stable".to_string(),
+            ChannelVersion {
+                version: "1.85.0".to_string(),
+                date: "2025-01-01".to_string(),
+            },
+        );
+        let cache = ManifestCache {
+            fetched_at_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            channels,
+        };
+        std::fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+
+        let config = config_with_rust_versions(vec!["stable", "1.80.0"]);
+        let opts = ResolveOptions {
+            cache_path: cache_path.clone(),
+            ..Default::default()
+        };
+        let resolved = config.resolve_versions(&opts);
+        assert_eq!(
+            resolved.versions["rust"],
+            vec!["1.85.0".to_string(), "1.80.0".to_string()]
+        );
+
+        std::fs::remove_file(&cache_path).ok();
+    }
+
+    /// Seeds a fresh (and otherwise irrelevant) cache so `load_or_fetch`
+    /// never falls through to `fetch_manifest`'s live network call: `"MSRV"`
+    /// only ever resolves from `opts.msrv`, never from the cache.
+    #[test]
+    fn leaves_msrv_unresolved_without_an_override() {
+        let cache_path = std::env::temp_dir().join(format!(
+            "json2vars-setter-rs-test-{}-msrv-no-override.json",
+            std::process::id()
+        ));
+        let cache = ManifestCache {
+            fetched_at_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            channels: Map::new(),
+        };
+        std::fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+
+        let config = config_with_rust_versions(vec!["MSRV"]);
+        let opts = ResolveOptions {
+            cache_path: cache_path.clone(),
+            ..Default::default()
+        };
+        let resolved = config.resolve_versions(&opts);
+        assert_eq!(resolved.versions["rust"], vec!["MSRV".to_string()]);
+
+        std::fs::remove_file(&cache_path).ok();
+    }
+
+    /// Seeds a fresh cache for the same reason as
+    /// `leaves_msrv_unresolved_without_an_override`: the config has a
+    /// symbolic `rust` entry, so `resolve_versions` consults the cache
+    /// before falling back to `fetch_manifest`'s live network call.
+    #[test]
+    fn resolves_msrv_from_override() {
+        let cache_path = std::env::temp_dir().join(format!(
+            "json2vars-setter-rs-test-{}-msrv-override.json",
+            std::process::id()
+        ));
+        let cache = ManifestCache {
+            fetched_at_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            channels: Map::new(),
+        };
+        std::fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+
+        let config = config_with_rust_versions(vec!["MSRV"]);
+        let opts = ResolveOptions {
+            cache_path: cache_path.clone(),
+            msrv: Some("1.74.0".to_string()),
+            ..Default::default()
+        };
+        let resolved = config.resolve_versions(&opts);
+        assert_eq!(resolved.versions["rust"], vec!["1.74.0".to_string()]);
+
+        std::fs::remove_file(&cache_path).ok();
+    }
+}
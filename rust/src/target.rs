@@ -0,0 +1,165 @@
+//! Derives each `os` matrix entry's canonical Rust target triple, following
+//! the elaborated platform metadata model nixpkgs uses (`rustcTarget`,
+//! `cargoShortTarget`, and arch/vendor/os/abi components).
+
+use crate::Config;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A Rust platform's target-triple components.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TargetSpec {
+    #[serde(rename = "rustcTarget")]
+    pub rustc_target: String,
+    #[serde(rename = "cargoShortTarget")]
+    pub cargo_short_target: String,
+    pub arch: String,
+    pub vendor: String,
+    pub os: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abi: Option<String>,
+}
+
+impl TargetSpec {
+    fn from_triple(triple: &str) -> Self {
+        let parts: Vec<&str> = triple.split('-').collect();
+        let (arch, vendor, os, abi) = match parts.as_slice() {
+            [arch, vendor, os, abi] => (*arch, *vendor, *os, Some(abi.to_string())),
+            [arch, vendor, os] => (*arch, *vendor, *os, None),
+            _ => (triple, "", "", None),
+        };
+
+        TargetSpec {
+            rustc_target: triple.to_string(),
+            // None of nixpkgs' vendor-stripped short forms are valid rustc
+            // target triples for the runners in `KNOWN_RUNNERS` (e.g.
+            // `x86_64-linux-gnu` isn't in `rustc --print target-list`), so
+            // for now this is always the same as `rustc_target`.
+            cargo_short_target: triple.to_string(),
+            arch: arch.to_string(),
+            vendor: vendor.to_string(),
+            os: os.to_string(),
+            abi,
+        }
+    }
+}
+
+/// GitHub-hosted runner labels known to `with_targets`, mapped to their
+/// canonical Rust target triple.
+const KNOWN_RUNNERS: &[(&str, &str)] = &[
+    ("ubuntu-latest", "x86_64-unknown-linux-gnu"),
+    ("ubuntu-24.04", "x86_64-unknown-linux-gnu"),
+    ("ubuntu-22.04", "x86_64-unknown-linux-gnu"),
+    ("ubuntu-20.04", "x86_64-unknown-linux-gnu"),
+    ("windows-latest", "x86_64-pc-windows-msvc"),
+    ("windows-2022", "x86_64-pc-windows-msvc"),
+    ("windows-2019", "x86_64-pc-windows-msvc"),
+    ("macos-latest", "aarch64-apple-darwin"),
+    ("macos-14", "aarch64-apple-darwin"),
+    ("macos-13", "x86_64-apple-darwin"),
+];
+
+fn lookup(os: &str) -> Option<&'static str> {
+    KNOWN_RUNNERS
+        .iter()
+        .find(|(label, _)| *label == os)
+        .map(|(_, triple)| *triple)
+}
+
+impl Config {
+    /// Returns a copy of this config with `targets` populated: each `os`
+    /// entry mapped to its canonical Rust target triple, decomposed into
+    /// arch/vendor/os/abi. `target_overrides` takes precedence over the
+    /// built-in GitHub-hosted runner table for self-hosted or non-standard
+    /// labels. An `os` entry with neither an override nor a known mapping is
+    /// left out of `targets`.
+    pub fn with_targets(&self) -> Config {
+        let mut config = self.clone();
+        config.targets = config
+            .os
+            .iter()
+            .filter_map(|os| {
+                let triple = config
+                    .target_overrides
+                    .get(os)
+                    .map(String::as_str)
+                    .or_else(|| lookup(os))?;
+                Some((os.clone(), TargetSpec::from_triple(triple)))
+            })
+            .collect();
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as Map;
+
+    fn config(os: Vec<&str>, target_overrides: Map<String, String>) -> Config {
+        Config {
+            os: os.into_iter().map(String::from).collect(),
+            target_overrides,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn resolves_known_runners() {
+        let resolved = config(vec!["ubuntu-latest", "windows-latest"], Map::new()).with_targets();
+        assert_eq!(
+            resolved.targets["ubuntu-latest"].rustc_target,
+            "x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(resolved.targets["ubuntu-latest"].arch, "x86_64");
+        assert_eq!(
+            resolved.targets["ubuntu-latest"].abi.as_deref(),
+            Some("gnu")
+        );
+        assert_eq!(
+            resolved.targets["ubuntu-latest"].cargo_short_target,
+            "x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            resolved.targets["windows-latest"].cargo_short_target,
+            "x86_64-pc-windows-msvc"
+        );
+    }
+
+    #[test]
+    fn macos_has_no_abi_component() {
+        let resolved = config(vec!["macos-latest"], Map::new()).with_targets();
+        let target = &resolved.targets["macos-latest"];
+        assert_eq!(target.rustc_target, "aarch64-apple-darwin");
+        assert_eq!(target.abi, None);
+        assert_eq!(target.cargo_short_target, "aarch64-apple-darwin");
+    }
+
+    #[test]
+    fn override_wins_over_known_table() {
+        let mut overrides = Map::new();
+        overrides.insert(
+            "self-hosted-pi".to_string(),
+            "aarch64-unknown-linux-gnu".to_string(),
+        );
+        overrides.insert(
+            "macos-latest".to_string(),
+            "x86_64-apple-darwin".to_string(),
+        );
+        let resolved = config(vec!["self-hosted-pi", "macos-latest"], overrides).with_targets();
+        assert_eq!(
+            resolved.targets["self-hosted-pi"].rustc_target,
+            "aarch64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            resolved.targets["macos-latest"].rustc_target,
+            "x86_64-apple-darwin"
+        );
+    }
+
+    #[test]
+    fn unknown_os_without_override_is_left_out() {
+        let resolved = config(vec!["some-custom-runner"], Map::new()).with_targets();
+        assert!(!resolved.targets.contains_key("some-custom-runner"));
+    }
+}
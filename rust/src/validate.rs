@@ -0,0 +1,190 @@
+//! Validation for matrix config files, returning precise per-field
+//! diagnostics instead of the bare `None` that [`crate::parse_config`]
+//! gives up on.
+
+use crate::{Config, ConfigFormat};
+use std::fmt;
+use std::path::Path;
+
+const KNOWN_FIELDS: [&str; 7] = [
+    "os",
+    "versions",
+    "ghpages_branch",
+    "target_overrides",
+    "targets",
+    "include",
+    "exclude",
+];
+
+/// One problem found while validating a config file against the [`Config`]
+/// schema: an unknown key, a field with the wrong type, or a field that
+/// fails a semantic check (e.g. an empty `os` list).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Validates the config file at `path` against the shape of [`Config`] and,
+/// if it is valid, returns the deserialized struct. On failure, returns every
+/// issue found rather than stopping at the first one, so a workflow author
+/// fixing a matrix file can address all of them in one pass.
+///
+/// The per-field checks are hand-written and do not use
+/// [`Config::json_schema`]: they cover unknown keys and the types of `os`,
+/// `versions` and `ghpages_branch` (plus a non-empty `os`). The other known
+/// fields are checked only by the final deserialization, whose error is
+/// reported as a single `<file>` issue.
+pub fn validate_config(path: &Path) -> Result<Config, Vec<ValidationIssue>> {
+    let format = ConfigFormat::from_path(path).ok_or_else(|| {
+        vec![ValidationIssue {
+            field: "<file>".to_string(),
+            message: "unrecognized or unsupported config extension".to_string(),
+        }]
+    })?;
+
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        vec![ValidationIssue {
+            field: "<file>".to_string(),
+            message: e.to_string(),
+        }]
+    })?;
+
+    let value = format.deserialize_value(&contents).map_err(|e| {
+        vec![ValidationIssue {
+            field: "<file>".to_string(),
+            message: format!("not valid {format:?}: {e}"),
+        }]
+    })?;
+
+    let issues = check_value(&value);
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+
+    format.deserialize(&contents).map_err(|e| {
+        vec![ValidationIssue {
+            field: "<file>".to_string(),
+            message: e,
+        }]
+    })
+}
+
+fn check_value(value: &serde_json::Value) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    let Some(object) = value.as_object() else {
+        issues.push(ValidationIssue {
+            field: "<root>".to_string(),
+            message: "expected an object with os, versions, and ghpages_branch".to_string(),
+        });
+        return issues;
+    };
+
+    for key in object.keys() {
+        if !KNOWN_FIELDS.contains(&key.as_str()) {
+            issues.push(ValidationIssue {
+                field: key.clone(),
+                message: "unknown field".to_string(),
+            });
+        }
+    }
+
+    match object.get("os") {
+        None => {}
+        Some(serde_json::Value::Array(items)) => {
+            if items.is_empty() {
+                issues.push(ValidationIssue {
+                    field: "os".to_string(),
+                    message: "must not be empty".to_string(),
+                });
+            } else if !items.iter().all(|v| v.is_string()) {
+                issues.push(ValidationIssue {
+                    field: "os".to_string(),
+                    message: "must be an array of strings".to_string(),
+                });
+            }
+        }
+        Some(_) => issues.push(ValidationIssue {
+            field: "os".to_string(),
+            message: "must be an array of strings".to_string(),
+        }),
+    }
+
+    match object.get("versions") {
+        None => {}
+        Some(serde_json::Value::Object(map)) => {
+            for (lang, entries) in map {
+                let valid = entries
+                    .as_array()
+                    .is_some_and(|items| items.iter().all(|v| v.is_string()));
+                if !valid {
+                    issues.push(ValidationIssue {
+                        field: format!("versions.{lang}"),
+                        message: "must be an array of strings".to_string(),
+                    });
+                }
+            }
+        }
+        Some(_) => issues.push(ValidationIssue {
+            field: "versions".to_string(),
+            message: "must be an object mapping language to a list of version strings".to_string(),
+        }),
+    }
+
+    match object.get("ghpages_branch") {
+        None => {}
+        Some(serde_json::Value::String(_)) => {}
+        Some(_) => issues.push(ValidationIssue {
+            field: "ghpages_branch".to_string(),
+            message: "must be a string".to_string(),
+        }),
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn accepts_valid_config() {
+        let file = write_temp(
+            r#"{"os": ["ubuntu-latest"], "versions": {"rust": ["stable"]}, "ghpages_branch": "gh-pages"}"#,
+        );
+        assert!(validate_config(file.path()).is_ok());
+    }
+
+    #[test]
+    fn reports_unknown_key_and_empty_os() {
+        let file = write_temp(
+            r#"{"os": [], "versions": {"rust": ["stable"]}, "ghpages_branch": "gh-pages", "typo": true}"#,
+        );
+        let issues = validate_config(file.path()).unwrap_err();
+        assert!(issues.iter().any(|i| i.field == "os"));
+        assert!(issues.iter().any(|i| i.field == "typo"));
+    }
+
+    #[test]
+    fn reports_wrong_type() {
+        let file = write_temp(
+            r#"{"os": ["ubuntu-latest"], "versions": {"rust": "stable"}, "ghpages_branch": "gh-pages"}"#,
+        );
+        let issues = validate_config(file.path()).unwrap_err();
+        assert!(issues.iter().any(|i| i.field == "versions.rust"));
+    }
+}